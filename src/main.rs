@@ -1,8 +1,13 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 use std::{env, fs};
 
 #[derive(Parser)]
@@ -14,19 +19,41 @@ struct Cli {
 
     /// MCP server name to show (shorthand for 'show <name>')
     name: Option<String>,
+
+    /// Output format for `list` (color is auto-disabled for non-text formats)
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    format: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Table,
+    Json,
+    Csv,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// List all MCP servers across all projects
-    List,
-    /// Add an MCP server to the current project
+    List {
+        /// Only list servers carrying this tag
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Add an MCP server (or every server in a --group) to the current project
     Add {
-        /// Name of the MCP server to add
-        name: String,
+        /// Name of the MCP server to add (omit when using --group)
+        name: Option<String>,
         /// Source project to copy configuration from (use partial path match)
         #[arg(long)]
         from: Option<String>,
+        /// Expand ${VAR}/.env placeholders before writing, instead of keeping them literal
+        #[arg(long)]
+        expand_env: bool,
+        /// Add every server tagged with this group in one pass
+        #[arg(long)]
+        group: Option<String>,
     },
     /// Remove an MCP server from the current project
     Remove {
@@ -38,6 +65,32 @@ enum Commands {
         /// Name of the MCP server
         name: String,
     },
+    /// Validate MCP servers by performing the initialize handshake
+    Doctor {
+        /// Name of the MCP server to check (checks all if omitted)
+        name: Option<String>,
+    },
+    /// Export MCP servers to a portable bundle file
+    Export {
+        /// Names of the MCP servers to include in the bundle
+        names: Vec<String>,
+        /// Output file path for the bundle (.json or .toml)
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Import MCP servers from a portable bundle file
+    Import {
+        /// Path to the bundle file to import
+        file: PathBuf,
+    },
+    /// Reconcile a server's configuration across all projects
+    Sync {
+        /// Name of the MCP server to sync
+        name: String,
+        /// Canonical project to sync from (use partial path match)
+        #[arg(long)]
+        from: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +105,9 @@ struct McpServer {
     args: Vec<String>,
     #[serde(default)]
     env: HashMap<String, String>,
+    /// Named groups this server belongs to (e.g. "backend"), for bulk `add --group`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
 }
 
 impl McpServer {
@@ -84,10 +140,19 @@ struct ClaudeJson {
     projects: HashMap<String, ProjectConfig>,
 }
 
+/// Which file an `McpEntry` was actually loaded from, so callers that need
+/// to write back know whether ~/.claude.json is the real source of truth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntrySource {
+    ClaudeJson,
+    McpJson,
+}
+
 #[derive(Debug, Clone)]
 struct McpEntry {
     server: McpServer,
     source_project: String,
+    source: EntrySource,
 }
 
 fn get_claude_json_path() -> Option<PathBuf> {
@@ -126,6 +191,7 @@ fn collect_all_mcp_servers() -> HashMap<String, Vec<McpEntry>> {
                 let entry = McpEntry {
                     server,
                     source_project: project_path.clone(),
+                    source: EntrySource::ClaudeJson,
                 };
                 all_servers.entry(name).or_default().push(entry);
             }
@@ -145,6 +211,7 @@ fn collect_all_mcp_servers() -> HashMap<String, Vec<McpEntry>> {
                     let entry = McpEntry {
                         server,
                         source_project: project_path.clone(),
+                        source: EntrySource::McpJson,
                     };
                     all_servers.entry(name).or_default().push(entry);
                 }
@@ -183,6 +250,20 @@ fn get_current_project_mcp_servers() -> HashMap<String, McpServer> {
         }
     }
 
+    // Resolve ${VAR}/$VAR placeholders in args and env against the process
+    // environment and the project's .env file, leaving unresolved ones literal.
+    if let Some(ref cwd_path) = cwd {
+        let dotenv = parse_dotenv(&cwd_path.join(".env"));
+        for server in servers.values_mut() {
+            for arg in &mut server.args {
+                *arg = expand_env_string(arg, &dotenv);
+            }
+            for value in server.env.values_mut() {
+                *value = expand_env_string(value, &dotenv);
+            }
+        }
+    }
+
     servers
 }
 
@@ -193,6 +274,119 @@ fn normalize_args(args: &[String], project_path: &str) -> Vec<String> {
         .collect()
 }
 
+/// Parse a simple `.env` file of `KEY=VALUE` lines, ignoring blank lines,
+/// `#` comments, and stripping matching quotes from values.
+fn parse_dotenv(path: &Path) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+    if let Ok(content) = fs::read_to_string(path) {
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let key = key.trim().to_string();
+                let mut value = value.trim().to_string();
+                if (value.starts_with('"') && value.ends_with('"') && value.len() >= 2)
+                    || (value.starts_with('\'') && value.ends_with('\'') && value.len() >= 2)
+                {
+                    value = value[1..value.len() - 1].to_string();
+                }
+                vars.insert(key, value);
+            }
+        }
+    }
+    vars
+}
+
+/// Resolve a `${VAR}` or `$VAR` reference against the process environment
+/// first, then the project's `.env` file.
+fn resolve_var(name: &str, dotenv: &HashMap<String, String>) -> Option<String> {
+    env::var(name).ok().or_else(|| dotenv.get(name).cloned())
+}
+
+/// Find every `${VAR}`/`$VAR` reference in a string, in order of appearance,
+/// as `(name, byte_start, byte_end)` spans covering the whole reference
+/// (including the `$`/`{`/`}`) so callers can substitute without re-matching
+/// already-expanded text.
+fn find_placeholder_spans(value: &str) -> Vec<(String, usize, usize)> {
+    let bytes = value.as_bytes();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'$' {
+            if i + 1 < bytes.len() && bytes[i + 1] == b'{' {
+                if let Some(len) = value[i + 2..].find('}') {
+                    spans.push((value[i + 2..i + 2 + len].to_string(), i, i + 2 + len + 1));
+                    i += 2 + len + 1;
+                    continue;
+                }
+            } else {
+                let start = i + 1;
+                let mut end = start;
+                while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_') {
+                    end += 1;
+                }
+                if end > start {
+                    spans.push((value[start..end].to_string(), i, end));
+                    i = end;
+                    continue;
+                }
+            }
+        }
+        i += 1;
+    }
+    spans
+}
+
+/// Expand every resolvable `${VAR}`/`$VAR` reference in `value`, leaving
+/// references that resolve to nothing untouched. Substitutes at each
+/// reference's matched span in a single left-to-right pass so one variable
+/// name being a prefix of another (`$FOO` vs `$FOOBAR`) can't corrupt the
+/// other's expansion.
+fn expand_env_string(value: &str, dotenv: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut last_end = 0;
+    for (name, start, end) in find_placeholder_spans(value) {
+        if let Some(resolved) = resolve_var(&name, dotenv) {
+            result.push_str(&value[last_end..start]);
+            result.push_str(&resolved);
+            last_end = end;
+        }
+    }
+    result.push_str(&value[last_end..]);
+    result
+}
+
+/// Names of placeholders in `value` that could not be resolved.
+fn unresolved_placeholders(value: &str, dotenv: &HashMap<String, String>) -> Vec<String> {
+    find_placeholder_spans(value)
+        .into_iter()
+        .filter(|(name, _, _)| resolve_var(name, dotenv).is_none())
+        .map(|(name, _, _)| name)
+        .collect()
+}
+
+/// All unresolved placeholder names referenced anywhere in a server's config.
+fn collect_unresolved_vars(server: &McpServer, dotenv: &HashMap<String, String>) -> Vec<String> {
+    let mut vars = Vec::new();
+    if let Some(ref cmd) = server.command {
+        vars.extend(unresolved_placeholders(cmd, dotenv));
+    }
+    if let Some(ref url) = server.url {
+        vars.extend(unresolved_placeholders(url, dotenv));
+    }
+    for arg in &server.args {
+        vars.extend(unresolved_placeholders(arg, dotenv));
+    }
+    for value in server.env.values() {
+        vars.extend(unresolved_placeholders(value, dotenv));
+    }
+    vars.sort();
+    vars.dedup();
+    vars
+}
+
 fn configs_differ(entries: &[McpEntry]) -> bool {
     if entries.len() <= 1 {
         return false;
@@ -209,13 +403,176 @@ fn configs_differ(entries: &[McpEntry]) -> bool {
     })
 }
 
-fn list_mcp_servers() {
+/// Standard edit-distance DP: minimum single-character insertions,
+/// deletions, or substitutions to turn `a` into `b`.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b_chars.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let up = row[j + 1];
+            let cost = if a_char == *b_char { 0 } else { 1 };
+            row[j + 1] = (row[j] + 1).min(up + 1).min(diag + cost);
+            diag = up;
+        }
+    }
+
+    row[b_chars.len()]
+}
+
+/// The closest known server names to `name`, nearest first, capped at three.
+fn suggest_similar_names(name: &str, all_servers: &HashMap<String, Vec<McpEntry>>) -> Vec<String> {
+    let threshold = (name.len() / 3).max(2);
+    let lower = name.to_lowercase();
+
+    let mut candidates: Vec<(&String, usize)> = all_servers
+        .keys()
+        .map(|candidate| (candidate, levenshtein_distance(&lower, &candidate.to_lowercase())))
+        .filter(|(_, distance)| *distance <= threshold)
+        .collect();
+
+    candidates.sort_by_key(|(_, distance)| *distance);
+    candidates
+        .into_iter()
+        .take(3)
+        .map(|(candidate, _)| candidate.clone())
+        .collect()
+}
+
+/// Print a Cargo-style "did you mean" line if any known server name is close.
+fn print_did_you_mean(name: &str, all_servers: &HashMap<String, Vec<McpEntry>>) {
+    let suggestions = suggest_similar_names(name, all_servers);
+    if !suggestions.is_empty() {
+        eprintln!("  did you mean: {}", suggestions.join(", ").cyan());
+    }
+}
+
+/// A single server's inventory data, shaped for machine-readable output.
+#[derive(Debug, Serialize)]
+struct ServerListing {
+    name: String,
+    #[serde(rename = "type")]
+    server_type: String,
+    target: String,
+    projects: Vec<String>,
+    has_diff: bool,
+}
+
+fn build_listings(all_servers: &HashMap<String, Vec<McpEntry>>) -> Vec<ServerListing> {
+    let mut names: Vec<_> = all_servers.keys().collect();
+    names.sort();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let entries = &all_servers[name];
+            let baseline = &entries[0];
+            let mut projects: Vec<String> = entries.iter().map(|e| e.source_project.clone()).collect();
+            projects.sort();
+
+            ServerListing {
+                name: name.clone(),
+                server_type: if baseline.server.url.is_some() { "url" } else { "command" }.to_string(),
+                target: baseline.server.display_target().to_string(),
+                projects,
+                has_diff: configs_differ(entries),
+            }
+        })
+        .collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn print_table(listings: &[ServerListing]) {
+    let name_w = listings.iter().map(|l| l.name.len()).max().unwrap_or(0).max(4);
+    let type_w = listings.iter().map(|l| l.server_type.len()).max().unwrap_or(0).max(4);
+    let target_w = listings.iter().map(|l| l.target.len()).max().unwrap_or(0).max(6);
+
+    println!(
+        "{:name_w$}  {:type_w$}  {:target_w$}  {:8}  DIFF",
+        "NAME", "TYPE", "TARGET", "PROJECTS"
+    );
+    for l in listings {
+        println!(
+            "{:name_w$}  {:type_w$}  {:target_w$}  {:8}  {}",
+            l.name,
+            l.server_type,
+            l.target,
+            l.projects.len(),
+            if l.has_diff { "yes" } else { "no" }
+        );
+    }
+}
+
+fn print_csv(listings: &[ServerListing]) {
+    println!("name,type,target,project,has_diff");
+    for l in listings {
+        for project in &l.projects {
+            println!(
+                "{},{},{},{},{}",
+                csv_field(&l.name),
+                l.server_type,
+                csv_field(&l.target),
+                csv_field(project),
+                l.has_diff
+            );
+        }
+    }
+}
+
+fn print_json(listings: &[ServerListing]) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(listings).expect("Failed to serialize server listing")
+    );
+}
+
+/// The sorted, deduplicated union of tags across every config of a server.
+fn entry_tags(entries: &[McpEntry]) -> Vec<String> {
+    let mut tags: Vec<String> = entries.iter().flat_map(|e| e.server.tags.clone()).collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+fn list_mcp_servers(format: OutputFormat, tag: Option<&str>) {
     let all_servers = collect_all_mcp_servers();
+    let all_servers: HashMap<String, Vec<McpEntry>> = match tag {
+        Some(t) => all_servers
+            .into_iter()
+            .filter(|(_, entries)| entries.iter().any(|e| e.server.tags.iter().any(|tg| tg == t)))
+            .collect(),
+        None => all_servers,
+    };
+
+    if !matches!(format, OutputFormat::Text) {
+        let listings = build_listings(&all_servers);
+        match format {
+            OutputFormat::Table => print_table(&listings),
+            OutputFormat::Json => print_json(&listings),
+            OutputFormat::Csv => print_csv(&listings),
+            OutputFormat::Text => unreachable!(),
+        }
+        return;
+    }
+
     let current_servers = get_current_project_mcp_servers();
     let cwd = env::current_dir()
         .ok()
         .map(|p| p.to_string_lossy().to_string())
         .unwrap_or_default();
+    let dotenv = env::current_dir()
+        .map(|p| parse_dotenv(&p.join(".env")))
+        .unwrap_or_default();
 
     if all_servers.is_empty() {
         println!("No MCP servers found across any projects.");
@@ -262,6 +619,20 @@ fn list_mcp_servers() {
             } else {
                 println!("    {} {}", label.dimmed(), target);
             }
+
+            let unresolved = collect_unresolved_vars(&entry.server, &dotenv);
+            if !unresolved.is_empty() {
+                println!(
+                    "    {} {}",
+                    "needs:".yellow(),
+                    unresolved.join(", ").yellow()
+                );
+            }
+        }
+
+        let tags = entry_tags(entries);
+        if !tags.is_empty() {
+            println!("    {} {}", "tags:".dimmed(), tags.join(", "));
         }
 
         // Show projects using this server (sorted)
@@ -319,6 +690,10 @@ fn show_mcp_server(name: &str) {
 
             println!("{} {}", "MCP Server:".bold(), name.bold());
             println!("  {} {}", "Status:".dimmed(), status);
+            let tags = entry_tags(entries);
+            if !tags.is_empty() {
+                println!("  {} {}", "Tags:".dimmed(), tags.join(", "));
+            }
             println!();
 
             // Use first entry as baseline for comparison
@@ -386,18 +761,88 @@ fn show_mcp_server(name: &str) {
                     // Baseline has env but this one doesn't
                     println!("    {} {}", "env:".dimmed(), "(none)".yellow());
                 }
+
+                let dotenv = parse_dotenv(&PathBuf::from(&entry.source_project).join(".env"));
+                let unresolved = collect_unresolved_vars(&entry.server, &dotenv);
+                if !unresolved.is_empty() {
+                    println!(
+                        "    {} {}",
+                        "needs:".yellow(),
+                        unresolved.join(", ").yellow()
+                    );
+                }
                 println!();
             }
         }
         None => {
             eprintln!("{} MCP server '{}' not found", "Error:".red(), name);
+            print_did_you_mean(name, &all_servers);
             std::process::exit(1);
         }
     }
 }
 
-fn add_mcp_server(name: &str, from: Option<&str>) {
-    let all_servers = collect_all_mcp_servers();
+fn add_mcp_server(name: Option<&str>, from: Option<&str>, expand_env: bool, group: Option<&str>) {
+    match (name, group) {
+        (Some(name), None) => {
+            let all_servers = collect_all_mcp_servers();
+            if let Err(msg) = add_single_mcp_server(&all_servers, name, from, expand_env) {
+                eprintln!("{msg}");
+                std::process::exit(1);
+            }
+        }
+        (None, Some(group)) => {
+            let all_servers = collect_all_mcp_servers();
+            let mut names: Vec<&String> = all_servers
+                .iter()
+                .filter(|(_, entries)| entries.iter().any(|e| e.server.tags.iter().any(|t| t == group)))
+                .map(|(name, _)| name)
+                .collect();
+            names.sort();
+
+            if names.is_empty() {
+                eprintln!("{} No MCP servers tagged with group '{}'", "Error:".red(), group);
+                std::process::exit(1);
+            }
+
+            // Keep going on a per-server failure so one bad entry in the group
+            // doesn't leave the rest of the group unprocessed.
+            let mut failed = 0;
+            for name in &names {
+                if let Err(msg) = add_single_mcp_server(&all_servers, name, from, expand_env) {
+                    eprintln!("{msg}");
+                    failed += 1;
+                }
+            }
+
+            if failed > 0 {
+                eprintln!(
+                    "{} {} of {} server(s) in group '{}' failed to add",
+                    "Error:".red(),
+                    failed,
+                    names.len(),
+                    group
+                );
+                std::process::exit(1);
+            }
+        }
+        (Some(_), Some(_)) => {
+            eprintln!("{} Specify either a server name or --group, not both", "Error:".red());
+            std::process::exit(1);
+        }
+        (None, None) => {
+            eprintln!("{} Specify a server name or --group", "Error:".red());
+            std::process::exit(1);
+        }
+    }
+}
+
+fn add_single_mcp_server(
+    all_servers: &HashMap<String, Vec<McpEntry>>,
+    name: &str,
+    from: Option<&str>,
+    expand_env: bool,
+) -> Result<(), String> {
     let current_servers = get_current_project_mcp_servers();
     let cwd = env::current_dir().expect("Failed to get current directory");
     let cwd_str = cwd.to_string_lossy().to_string();
@@ -408,18 +853,18 @@ fn add_mcp_server(name: &str, from: Option<&str>) {
             "Note:".yellow(),
             name
         );
-        return;
+        return Ok(());
     }
 
     let entries = match all_servers.get(name) {
         Some(e) => e,
         None => {
-            eprintln!(
-                "{} MCP server '{}' not found in any project",
-                "Error:".red(),
-                name
-            );
-            std::process::exit(1);
+            let mut msg = format!("{} MCP server '{}' not found in any project", "Error:".red(), name);
+            let suggestions = suggest_similar_names(name, all_servers);
+            if !suggestions.is_empty() {
+                msg.push_str(&format!("\n  did you mean: {}", suggestions.join(", ").cyan()));
+            }
+            return Err(msg);
         }
     };
 
@@ -428,30 +873,28 @@ fn add_mcp_server(name: &str, from: Option<&str>) {
         match entries.iter().find(|e| e.source_project.contains(from_pattern)) {
             Some(e) => e,
             None => {
-                eprintln!(
-                    "{} No configuration found matching '{}'",
+                let mut msg = format!(
+                    "{} No configuration found matching '{}'\nAvailable configurations:",
                     "Error:".red(),
                     from_pattern
                 );
-                eprintln!("Available configurations:");
                 for e in entries {
-                    eprintln!("  - {}", shorten_path(&e.source_project));
+                    msg.push_str(&format!("\n  - {}", shorten_path(&e.source_project)));
                 }
-                std::process::exit(1);
+                return Err(msg);
             }
         }
     } else if entries.len() > 1 && configs_differ(entries) {
-        eprintln!(
+        let mut msg = format!(
             "{} Multiple configurations found for '{}'. Use --from to specify:",
             "Error:".red(),
             name
         );
         for e in entries {
-            eprintln!("  {} {}", "→".dimmed(), shorten_path(&e.source_project));
+            msg.push_str(&format!("\n  {} {}", "→".dimmed(), shorten_path(&e.source_project)));
         }
-        eprintln!();
-        eprintln!("Example: cc-mcp-admin add {} --from votingmachine", name);
-        std::process::exit(1);
+        msg.push_str(&format!("\n\nExample: cc-mcp-admin add {name} --from votingmachine"));
+        return Err(msg);
     } else {
         &entries[0]
     };
@@ -465,6 +908,18 @@ fn add_mcp_server(name: &str, from: Option<&str>) {
         }
     }
 
+    // Resolve ${VAR}/.env placeholders in place, or leave them literal so the
+    // secret itself is never persisted to ~/.claude.json.
+    if expand_env {
+        let dotenv = parse_dotenv(&cwd.join(".env"));
+        for arg in &mut server.args {
+            *arg = expand_env_string(arg, &dotenv);
+        }
+        for value in server.env.values_mut() {
+            *value = expand_env_string(value, &dotenv);
+        }
+    }
+
     // Update to ~/.claude.json
     let claude_json_path = get_claude_json_path().expect("Failed to get claude.json path");
     let content = fs::read_to_string(&claude_json_path).expect("Failed to read ~/.claude.json");
@@ -508,6 +963,8 @@ fn add_mcp_server(name: &str, from: Option<&str>) {
     if !server.args.is_empty() {
         println!("  {} {:?}", "args:".dimmed(), server.args);
     }
+
+    Ok(())
 }
 
 fn remove_mcp_server(name: &str) {
@@ -521,25 +978,19 @@ fn remove_mcp_server(name: &str) {
             "Error:".red(),
             name
         );
+        print_did_you_mean(name, &collect_all_mcp_servers());
         std::process::exit(1);
     }
 
     // Check if it's in local .mcp.json
-    let mcp_json_path = cwd.join(".mcp.json");
-    if mcp_json_path.exists() {
-        if let Ok(content) = fs::read_to_string(&mcp_json_path) {
-            if let Ok(mcp_json) = serde_json::from_str::<McpJsonFile>(&content) {
-                if mcp_json.mcp_servers.contains_key(name) {
-                    println!(
-                        "{} MCP server '{}' is defined in local .mcp.json",
-                        "Note:".yellow(),
-                        name
-                    );
-                    println!("  Please remove it manually from .mcp.json");
-                    return;
-                }
-            }
-        }
+    if project_mcp_json_has_server(&cwd_str, name) {
+        println!(
+            "{} MCP server '{}' is defined in local .mcp.json",
+            "Note:".yellow(),
+            name
+        );
+        println!("  Please remove it manually from .mcp.json");
+        return;
     }
 
     // Remove from ~/.claude.json
@@ -567,9 +1018,628 @@ fn remove_mcp_server(name: &str) {
     );
 }
 
+const MCP_PROTOCOL_VERSION: &str = "2024-11-05";
+const INITIALIZE_TIMEOUT: Duration = Duration::from_secs(10);
+/// The initialized notification has no response to wait on, so a slow or
+/// unresponsive server shouldn't delay reporting an already-determined Ok.
+const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outcome of an `initialize` handshake against one MCP server.
+#[derive(Debug)]
+enum DoctorOutcome {
+    Ok {
+        server_info: serde_json::Value,
+        capabilities: serde_json::Value,
+    },
+    SpawnError(String),
+    NonZeroExit(i32, String),
+    /// The response was not valid JSON-RPC at all.
+    MalformedJson(String),
+    /// The response parsed fine but the server's JSON-RPC `error` rejected initialize.
+    ProtocolError(String),
+    /// Carries any stderr captured before the child was killed.
+    Timeout(String),
+}
+
+fn build_initialize_request() -> String {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": MCP_PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": {
+                "name": "cc-mcp-admin",
+                "version": env!("CARGO_PKG_VERSION")
+            }
+        }
+    })
+    .to_string()
+}
+
+fn build_initialized_notification() -> String {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/initialized"
+    })
+    .to_string()
+}
+
+fn parse_initialize_response(value: &serde_json::Value) -> DoctorOutcome {
+    if let Some(error) = value.get("error") {
+        return DoctorOutcome::ProtocolError(error.to_string());
+    }
+    let Some(result) = value.get("result") else {
+        return DoctorOutcome::MalformedJson(format!("response has neither result nor error: {value}"));
+    };
+    DoctorOutcome::Ok {
+        server_info: result.get("serverInfo").cloned().unwrap_or(serde_json::Value::Null),
+        capabilities: result.get("capabilities").cloned().unwrap_or(serde_json::Value::Null),
+    }
+}
+
+/// Spawn a `command`-type server over stdio and run the initialize handshake.
+fn check_command_server(server: &McpServer) -> DoctorOutcome {
+    let command = match &server.command {
+        Some(c) => c,
+        None => return DoctorOutcome::SpawnError("server has no command configured".to_string()),
+    };
+
+    let mut child = match Command::new(command)
+        .args(&server.args)
+        .envs(&server.env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => return DoctorOutcome::SpawnError(e.to_string()),
+    };
+
+    let mut stdin = child.stdin.take().expect("stdin was piped");
+    let stdout = child.stdout.take().expect("stdout was piped");
+    let mut stderr = child.stderr.take().expect("stderr was piped");
+
+    let wrote = writeln!(stdin, "{}", build_initialize_request())
+        .and_then(|_| writeln!(stdin, "{}", build_initialized_notification()));
+    if let Err(e) = wrote {
+        let _ = child.kill();
+        let mut stderr_output = String::new();
+        let _ = stderr.read_to_string(&mut stderr_output);
+        let mut msg = format!("failed to write to child stdin: {e}");
+        if !stderr_output.trim().is_empty() {
+            msg.push_str(&format!("\nstderr: {}", stderr_output.trim()));
+        }
+        return DoctorOutcome::SpawnError(msg);
+    }
+    drop(stdin);
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let reader = BufReader::new(stdout);
+        for line in reader.lines().map_while(Result::ok) {
+            if let Ok(value) = serde_json::from_str::<serde_json::Value>(&line) {
+                if value.get("id").and_then(serde_json::Value::as_i64) == Some(1) {
+                    let _ = tx.send(value);
+                    return;
+                }
+            }
+        }
+    });
+
+    let response = rx.recv_timeout(INITIALIZE_TIMEOUT);
+
+    match response {
+        Ok(value) => {
+            let _ = child.kill();
+            parse_initialize_response(&value)
+        }
+        Err(_) => {
+            // Check for a genuine self-exit before killing: if we kill first,
+            // a child that exits right as we time out gets reaped by the kill
+            // and misclassified as a -1 NonZeroExit instead of a real timeout.
+            let exited = child.try_wait();
+            let _ = child.kill();
+
+            // Always drain stderr here: a server that logged an error before
+            // hanging shouldn't be reported as a bare timeout.
+            let mut stderr_output = String::new();
+            let _ = stderr.read_to_string(&mut stderr_output);
+
+            if let Ok(Some(status)) = exited {
+                if !status.success() {
+                    return DoctorOutcome::NonZeroExit(status.code().unwrap_or(-1), stderr_output);
+                }
+            }
+            DoctorOutcome::Timeout(stderr_output)
+        }
+    }
+}
+
+/// Build a POST request to `url` that sends each of `server`'s `env` entries
+/// as an HTTP header.
+fn build_post_request(url: &str, server: &McpServer, timeout: Duration) -> ureq::Request {
+    server.env.iter().fold(
+        ureq::post(url).set("Content-Type", "application/json").timeout(timeout),
+        |req, (key, value)| req.set(key, value),
+    )
+}
+
+/// POST the initialize handshake to a `url`-type server's HTTP/SSE endpoint.
+fn check_url_server(server: &McpServer) -> DoctorOutcome {
+    let url = match &server.url {
+        Some(u) => u,
+        None => return DoctorOutcome::SpawnError("server has no url configured".to_string()),
+    };
+
+    match build_post_request(url, server, INITIALIZE_TIMEOUT).send_string(&build_initialize_request()) {
+        Ok(response) => match response.into_json::<serde_json::Value>() {
+            Ok(value) => {
+                let outcome = parse_initialize_response(&value);
+                if matches!(outcome, DoctorOutcome::Ok { .. }) {
+                    // Complete the handshake like the command path does; this
+                    // is a one-shot probe so the notification's result is
+                    // ignored either way, and a short timeout keeps a slow
+                    // server from delaying the already-determined Ok result.
+                    let _ = build_post_request(url, server, NOTIFICATION_TIMEOUT)
+                        .send_string(&build_initialized_notification());
+                }
+                outcome
+            }
+            Err(e) => DoctorOutcome::MalformedJson(e.to_string()),
+        },
+        Err(ureq::Error::Status(code, response)) => {
+            DoctorOutcome::NonZeroExit(code as i32, response.into_string().unwrap_or_default())
+        }
+        Err(ureq::Error::Transport(e)) => {
+            // ureq reports connect/read timeouts as a Transport error with no
+            // dedicated variant; detect it from the message so a slow
+            // endpoint is classified as a timeout, not a spawn error.
+            if e.to_string().to_lowercase().contains("timed out") {
+                DoctorOutcome::Timeout(String::new())
+            } else {
+                DoctorOutcome::SpawnError(e.to_string())
+            }
+        }
+    }
+}
+
+fn print_doctor_result(name: &str, project: &str, outcome: &DoctorOutcome) {
+    match outcome {
+        DoctorOutcome::Ok { server_info, capabilities } => {
+            println!("  {} {}", "✓".green(), name.green().bold());
+            println!("    {} {}", "project:".dimmed(), shorten_path(project));
+            if !server_info.is_null() {
+                println!("    {} {}", "serverInfo:".dimmed(), server_info);
+            }
+            if !capabilities.is_null() {
+                println!("    {} {}", "capabilities:".dimmed(), capabilities);
+            }
+        }
+        DoctorOutcome::SpawnError(msg) => {
+            println!("  {} {} {}", "✗".red(), name.red().bold(), "(spawn error)".dimmed());
+            println!("    {} {}", "error:".dimmed(), msg);
+        }
+        DoctorOutcome::NonZeroExit(code, stderr) => {
+            println!(
+                "  {} {} {}",
+                "✗".red(),
+                name.red().bold(),
+                format!("(exited with code {code})").dimmed()
+            );
+            if !stderr.trim().is_empty() {
+                println!("    {} {}", "stderr:".dimmed(), stderr.trim());
+            }
+        }
+        DoctorOutcome::MalformedJson(msg) => {
+            println!("  {} {} {}", "✗".red(), name.red().bold(), "(malformed response)".dimmed());
+            println!("    {} {}", "error:".dimmed(), msg);
+        }
+        DoctorOutcome::ProtocolError(msg) => {
+            println!("  {} {} {}", "✗".red(), name.red().bold(), "(protocol error)".dimmed());
+            println!("    {} {}", "error:".dimmed(), msg);
+        }
+        DoctorOutcome::Timeout(stderr) => {
+            println!(
+                "  {} {} {}",
+                "✗".red(),
+                name.red().bold(),
+                "(timed out after 10s)".dimmed()
+            );
+            if !stderr.trim().is_empty() {
+                println!("    {} {}", "stderr:".dimmed(), stderr.trim());
+            }
+        }
+    }
+    println!();
+}
+
+fn doctor_mcp_servers(name: Option<&str>) {
+    let all_servers = collect_all_mcp_servers();
+
+    let mut names: Vec<String> = match name {
+        Some(n) => {
+            if !all_servers.contains_key(n) {
+                eprintln!("{} MCP server '{}' not found in any project", "Error:".red(), n);
+                std::process::exit(1);
+            }
+            vec![n.to_string()]
+        }
+        None => all_servers.keys().cloned().collect(),
+    };
+    names.sort();
+
+    if names.is_empty() {
+        println!("No MCP servers found across any projects.");
+        return;
+    }
+
+    println!("{}", "Checking MCP servers:".bold());
+    println!();
+
+    let mut ok_count = 0;
+    let mut fail_count = 0;
+
+    for name in &names {
+        let entry = &all_servers[name][0];
+        let outcome = if entry.server.url.is_some() {
+            check_url_server(&entry.server)
+        } else {
+            check_command_server(&entry.server)
+        };
+        if matches!(outcome, DoctorOutcome::Ok { .. }) {
+            ok_count += 1;
+        } else {
+            fail_count += 1;
+        }
+        print_doctor_result(name, &entry.source_project, &outcome);
+    }
+
+    println!("{}", format!("{ok_count} ok, {fail_count} failed").dimmed());
+}
+
+const BUNDLE_VERSION: u32 = 1;
+
+/// A portable, self-contained set of MCP server definitions.
+///
+/// Project-specific paths are normalized to `<PROJECT>` and secrets are
+/// lifted out into named `variables` rather than embedded in the file.
+#[derive(Debug, Serialize, Deserialize)]
+struct ServerBundle {
+    version: u32,
+    servers: HashMap<String, McpServer>,
+    #[serde(default)]
+    variables: Vec<String>,
+}
+
+fn is_toml_path(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("toml")
+}
+
+fn export_mcp_servers(names: &[String], out: &Path) {
+    let all_servers = collect_all_mcp_servers();
+    let mut servers = HashMap::new();
+    let mut variables = Vec::new();
+
+    for name in names {
+        let entries = match all_servers.get(name) {
+            Some(e) => e,
+            None => {
+                eprintln!("{} MCP server '{}' not found in any project", "Error:".red(), name);
+                std::process::exit(1);
+            }
+        };
+
+        let entry = &entries[0];
+        let mut server = entry.server.clone();
+        server.args = normalize_args(&server.args, &entry.source_project);
+
+        for (key, value) in server.env.iter_mut() {
+            if !value.is_empty() {
+                variables.push(key.clone());
+                *value = format!("${{{key}}}");
+            }
+        }
+
+        servers.insert(name.clone(), server);
+    }
+
+    variables.sort();
+    variables.dedup();
+
+    let count = servers.len();
+    let bundle = ServerBundle {
+        version: BUNDLE_VERSION,
+        servers,
+        variables,
+    };
+
+    let serialized = if is_toml_path(out) {
+        toml::to_string_pretty(&bundle).expect("Failed to serialize bundle as TOML")
+    } else {
+        serde_json::to_string_pretty(&bundle).expect("Failed to serialize bundle as JSON")
+    };
+    fs::write(out, serialized).expect("Failed to write bundle file");
+
+    println!(
+        "{} Exported {} server(s) to {}",
+        "✓".green(),
+        count,
+        out.display()
+    );
+    if !bundle.variables.is_empty() {
+        println!(
+            "  {} {}",
+            "variables:".dimmed(),
+            bundle.variables.join(", ")
+        );
+    }
+}
+
+/// Expand `<PROJECT>` and `${VAR}`/`$VAR` placeholders against the current
+/// project directory and a resolved set of bundle variables.
+fn expand_placeholders(value: &str, cwd: &str, variables: &HashMap<String, String>) -> String {
+    // Substitute at each reference's matched span in one left-to-right pass
+    // so one variable name being a prefix of another can't corrupt it.
+    let with_project = value.replace("<PROJECT>", cwd);
+    let mut result = String::with_capacity(with_project.len());
+    let mut last_end = 0;
+    for (name, start, end) in find_placeholder_spans(&with_project) {
+        if let Some(resolved) = variables.get(&name) {
+            result.push_str(&with_project[last_end..start]);
+            result.push_str(resolved);
+            last_end = end;
+        }
+    }
+    result.push_str(&with_project[last_end..]);
+    result
+}
+
+fn resolve_bundle_variables(variables: &[String]) -> HashMap<String, String> {
+    let mut resolved = HashMap::new();
+    for var in variables {
+        if let Ok(value) = env::var(var) {
+            resolved.insert(var.clone(), value);
+            continue;
+        }
+        print!("Enter value for {}: ", var.yellow());
+        let _ = std::io::stdout().flush();
+        let mut input = String::new();
+        std::io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read input");
+        resolved.insert(var.clone(), input.trim().to_string());
+    }
+    resolved
+}
+
+fn import_mcp_servers(file: &Path) {
+    let content = fs::read_to_string(file).expect("Failed to read bundle file");
+    let bundle: ServerBundle = if is_toml_path(file) {
+        toml::from_str(&content).expect("Failed to parse bundle as TOML")
+    } else {
+        serde_json::from_str(&content).expect("Failed to parse bundle as JSON")
+    };
+
+    let variables = resolve_bundle_variables(&bundle.variables);
+    let cwd = env::current_dir().expect("Failed to get current directory");
+    let cwd_str = cwd.to_string_lossy().to_string();
+
+    let claude_json_path = get_claude_json_path().expect("Failed to get claude.json path");
+    let content = fs::read_to_string(&claude_json_path).expect("Failed to read ~/.claude.json");
+    let mut json: serde_json::Value =
+        serde_json::from_str(&content).expect("Failed to parse ~/.claude.json");
+
+    if json.get("projects").is_none() {
+        json["projects"] = serde_json::json!({});
+    }
+    if json["projects"].get(&cwd_str).is_none() {
+        json["projects"][&cwd_str] = serde_json::json!({ "mcpServers": {} });
+    }
+    if json["projects"][&cwd_str].get("mcpServers").is_none() {
+        json["projects"][&cwd_str]["mcpServers"] = serde_json::json!({});
+    }
+
+    for (name, mut server) in bundle.servers {
+        server.args = server
+            .args
+            .iter()
+            .map(|arg| expand_placeholders(arg, &cwd_str, &variables))
+            .collect();
+        for value in server.env.values_mut() {
+            *value = expand_placeholders(value, &cwd_str, &variables);
+        }
+
+        json["projects"][&cwd_str]["mcpServers"][&name] = serde_json::to_value(&server).unwrap();
+        println!("{} Imported MCP server '{}'", "✓".green(), name.green().bold());
+    }
+
+    let new_content = serde_json::to_string_pretty(&json).expect("Failed to serialize JSON");
+    fs::write(&claude_json_path, new_content).expect("Failed to write ~/.claude.json");
+}
+
+/// Print what would change for one project if `new` replaced `old`.
+fn print_sync_diff(old: &McpServer, new: &McpServer) {
+    if old.command != new.command {
+        if let Some(ref cmd) = new.command {
+            println!("    {} {}", "command:".dimmed(), cmd.yellow());
+        }
+    }
+    if old.url != new.url {
+        if let Some(ref url) = new.url {
+            println!("    {} {}", "url:".dimmed(), url.yellow());
+        }
+    }
+    if old.args != new.args {
+        println!("    {} {:?}", "args:".dimmed(), new.args);
+    }
+    if old.env != new.env {
+        println!("    {} {:?}", "env:".dimmed(), new.env);
+    }
+    if old.command == new.command && old.url == new.url && old.args == new.args && old.env == new.env {
+        println!("    {}", "(no changes)".dimmed());
+    }
+}
+
+/// True if `project`'s local .mcp.json defines `name` (i.e. the real source
+/// of truth is the project file, not ~/.claude.json).
+fn project_mcp_json_has_server(project: &str, name: &str) -> bool {
+    let mcp_json_path = PathBuf::from(project).join(".mcp.json");
+    let Ok(content) = fs::read_to_string(&mcp_json_path) else {
+        return false;
+    };
+    let Ok(mcp_json) = serde_json::from_str::<McpJsonFile>(&content) else {
+        return false;
+    };
+    mcp_json.mcp_servers.contains_key(name)
+}
+
+fn sync_mcp_server(name: &str, from: Option<&str>) {
+    let all_servers = collect_all_mcp_servers();
+
+    let entries = match all_servers.get(name) {
+        Some(e) => e,
+        None => {
+            eprintln!("{} MCP server '{}' not found in any project", "Error:".red(), name);
+            print_did_you_mean(name, &all_servers);
+            std::process::exit(1);
+        }
+    };
+
+    // Select the canonical configuration the same way `add` does.
+    let canonical = if let Some(from_pattern) = from {
+        match entries.iter().find(|e| e.source_project.contains(from_pattern)) {
+            Some(e) => e,
+            None => {
+                eprintln!(
+                    "{} No configuration found matching '{}'",
+                    "Error:".red(),
+                    from_pattern
+                );
+                eprintln!("Available configurations:");
+                for e in entries {
+                    eprintln!("  - {}", shorten_path(&e.source_project));
+                }
+                std::process::exit(1);
+            }
+        }
+    } else if entries.len() > 1 && configs_differ(entries) {
+        eprintln!(
+            "{} Multiple configurations found for '{}'. Use --from to specify the canonical one:",
+            "Error:".red(),
+            name
+        );
+        for e in entries {
+            eprintln!("  {} {}", "→".dimmed(), shorten_path(&e.source_project));
+        }
+        eprintln!();
+        eprintln!("Example: cc-mcp-admin sync {} --from votingmachine", name);
+        std::process::exit(1);
+    } else {
+        &entries[0]
+    };
+
+    let canonical_args = normalize_args(&canonical.server.args, &canonical.source_project);
+
+    let mut targets: Vec<&McpEntry> = Vec::new();
+    for entry in entries.iter().filter(|e| e.source_project != canonical.source_project) {
+        if entry.source == EntrySource::McpJson {
+            println!(
+                "{} '{}' in {} is defined in .mcp.json, skipping (edit .mcp.json directly)",
+                "Warning:".yellow(),
+                name,
+                shorten_path(&entry.source_project).dimmed()
+            );
+            continue;
+        }
+        targets.push(entry);
+    }
+
+    if targets.is_empty() {
+        println!(
+            "{} '{}' is already in sync across all projects",
+            "✓".green(),
+            name
+        );
+        return;
+    }
+
+    println!(
+        "{} {} ({})",
+        "Canonical:".bold(),
+        name.bold(),
+        shorten_path(&canonical.source_project).dimmed()
+    );
+    println!();
+
+    let mut planned: Vec<(String, McpServer)> = Vec::new();
+
+    for target in &targets {
+        let mut server = canonical.server.clone();
+        server.args = canonical_args
+            .iter()
+            .map(|arg| arg.replace("<PROJECT>", &target.source_project))
+            .collect();
+
+        println!(
+            "{} {}",
+            "Project:".bold(),
+            shorten_path(&target.source_project).dimmed()
+        );
+        print_sync_diff(&target.server, &server);
+        println!();
+
+        planned.push((target.source_project.clone(), server));
+    }
+
+    print!("Apply {} change(s)? [y/N] ", planned.len());
+    let _ = std::io::stdout().flush();
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input).expect("Failed to read input");
+    if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+        println!("Aborted.");
+        return;
+    }
+
+    let claude_json_path = get_claude_json_path().expect("Failed to get claude.json path");
+    let content = fs::read_to_string(&claude_json_path).expect("Failed to read ~/.claude.json");
+    let mut json: serde_json::Value =
+        serde_json::from_str(&content).expect("Failed to parse ~/.claude.json");
+
+    for (project, server) in &planned {
+        if json.get("projects").is_none() {
+            json["projects"] = serde_json::json!({});
+        }
+        if json["projects"].get(project).is_none() {
+            json["projects"][project] = serde_json::json!({ "mcpServers": {} });
+        }
+        if json["projects"][project].get("mcpServers").is_none() {
+            json["projects"][project]["mcpServers"] = serde_json::json!({});
+        }
+        json["projects"][project]["mcpServers"][name] = serde_json::to_value(server).unwrap();
+    }
+
+    let new_content = serde_json::to_string_pretty(&json).expect("Failed to serialize JSON");
+    fs::write(&claude_json_path, new_content).expect("Failed to write ~/.claude.json");
+
+    println!(
+        "{} Synced '{}' to {} project(s)",
+        "✓".green(),
+        name.green().bold(),
+        planned.len()
+    );
+}
+
 fn main() {
     let cli = Cli::parse();
 
+    // Disable ANSI color for machine-readable formats and non-TTY output
+    if !matches!(cli.format, OutputFormat::Text) || !std::io::stdout().is_terminal() {
+        colored::control::set_override(false);
+    }
+
     // Handle shorthand: cc-mcp-admin <name> => cc-mcp-admin show <name>
     if let Some(name) = cli.name {
         show_mcp_server(&name);
@@ -577,9 +1647,16 @@ fn main() {
     }
 
     match cli.command {
-        Some(Commands::List) | None => list_mcp_servers(),
-        Some(Commands::Add { name, from }) => add_mcp_server(&name, from.as_deref()),
+        Some(Commands::List { tag }) => list_mcp_servers(cli.format, tag.as_deref()),
+        None => list_mcp_servers(cli.format, None),
+        Some(Commands::Add { name, from, expand_env, group }) => {
+            add_mcp_server(name.as_deref(), from.as_deref(), expand_env, group.as_deref())
+        }
         Some(Commands::Remove { name }) => remove_mcp_server(&name),
         Some(Commands::Show { name }) => show_mcp_server(&name),
+        Some(Commands::Doctor { name }) => doctor_mcp_servers(name.as_deref()),
+        Some(Commands::Export { names, out }) => export_mcp_servers(&names, &out),
+        Some(Commands::Import { file }) => import_mcp_servers(&file),
+        Some(Commands::Sync { name, from }) => sync_mcp_server(&name, from.as_deref()),
     }
 }